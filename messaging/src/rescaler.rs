@@ -2,8 +2,6 @@ use common::adaptation::{deploy::DeploymentInfo, Rescaler, ServerfulScalingState
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-/// Factor for moving average.
-const MOVING_FACTOR: f64 = 0.25;
 /// Value to force spin up.
 const FORCE_THRESHOLD: f64 = 1e-4;
 /// Lambda overheads
@@ -11,6 +9,104 @@ const MIN_LAMBDA_OVERHEAD: f64 = 0.007;
 const MAX_LAMBDA_OVERHEAD: f64 = 0.020;
 /// Hourly price of 1vcpu and 2GB.
 const ECS_BASE_PRICE: f64 = 0.015;
+/// Time constant (in seconds) for the peak-EWMA decay. Chosen as a small
+/// multiple of the expected rescale period so a handful of missed/irregular
+/// rescale calls don't swing the estimate too far.
+const EWMA_TAU_SECS: f64 = 120.0;
+/// Default quantile used when a deployment asks for quantile-based scaling
+/// without specifying one (p90).
+const DEFAULT_ACTIVITY_QUANTILE: f64 = 0.9;
+/// Max number of samples kept in the forward-decaying activity reservoir.
+const RESERVOIR_CAPACITY: usize = 64;
+/// Decay rate (per second) applied to reservoir sample weights. Roughly a
+/// 10-minute half-life of relevance.
+const RESERVOIR_DECAY_LAMBDA: f64 = 0.00115;
+/// How long to let the reservoir's landmark age before rebasing sample
+/// weights around a fresh one, so weights don't grow unbounded.
+const LANDMARK_RESET_SECS: f64 = 3600.0;
+/// Target per-instance activity (concurrent in-flight work) to scale
+/// towards when graduating the replica count past a single instance.
+const TARGET_INSTANCE_UTILIZATION: f64 = 0.75;
+/// Price ratio at or above which we spin up (hysteresis upper bound); a ratio
+/// between 1 and this value does not spin up.
+const SCALE_UP_THRESHOLD: f64 = 1.1;
+/// Price ratio below which we tear down, even if above 0 (hysteresis lower
+/// bound). Kept well below `SCALE_UP_THRESHOLD` so a ratio hovering near 1
+/// doesn't flap.
+const SCALE_DOWN_THRESHOLD: f64 = 0.8;
+/// Minimum time since the last scale change before a deployment whose ratio
+/// is below `SCALE_DOWN_THRESHOLD` is actually torn down.
+const SCALE_DOWN_COOLDOWN_SECS: f64 = 300.0;
+
+/// A single observation in the forward-decaying activity reservoir, weighted
+/// by its age relative to the reservoir's landmark time.
+#[derive(Clone, Serialize, Deserialize)]
+struct DecayedSample {
+    value: f64,
+    weight: f64,
+}
+
+/// A candidate ECS instance shape the rescaler can choose to provision.
+/// `weight` is its capacity relative to a baseline shape of weight 1.0, such
+/// as the default candidate built from the deployment's `mem` (e.g. a
+/// larger box with `weight: 2.0` absorbs twice the activity), analogous to
+/// weighted load balancing across heterogeneous backends.
+#[derive(Clone, Serialize, Deserialize)]
+struct InstanceCandidate {
+    name: String,
+    price_per_hour: f64,
+    weight: f64,
+}
+
+/// Messaging-specific scaling knobs. These are read straight out of the
+/// deployment JSON rather than off `DeploymentInfo::msg_info` so that
+/// deployments without them (the common case) still parse; every field is
+/// `#[serde(default)]` for that reason.
+#[derive(Deserialize, Default)]
+struct MessagingScalingConfig {
+    #[serde(default)]
+    use_quantile_scaling: bool,
+    #[serde(default)]
+    activity_quantile: Option<f64>,
+    /// Candidate ECS shapes to provision. When absent, the rescaler falls
+    /// back to a single shape derived from the deployment's `mem`.
+    #[serde(default)]
+    instance_candidates: Option<Vec<InstanceCandidate>>,
+}
+
+/// Wrapper matching where `MessagingScalingConfig` lives in the deployment
+/// JSON, alongside the rest of `DeploymentInfo::msg_info`.
+#[derive(Deserialize, Default)]
+struct DeploymentScalingConfig {
+    #[serde(default)]
+    msg_info: Option<MessagingScalingConfig>,
+}
+
+/// The cheapest instance shape (and how many of it) that covers the given
+/// activity at the target utilization, among `candidates`. Returns `None` if
+/// no candidate is affordable (its total cost would exceed `budget`).
+fn cheapest_fit(
+    candidates: &[InstanceCandidate],
+    activity: f64,
+    budget: f64,
+) -> Option<(InstanceCandidate, u64)> {
+    candidates
+        .iter()
+        .map(|candidate| {
+            let count = if activity > 0.0 {
+                (activity / (TARGET_INSTANCE_UTILIZATION * candidate.weight)).ceil() as u64
+            } else {
+                0
+            };
+            (candidate.clone(), count)
+        })
+        .filter(|(candidate, count)| (*count as f64) * candidate.price_per_hour <= budget)
+        .min_by(|(a, a_count), (b, b_count)| {
+            let a_cost = (*a_count as f64) * a.price_per_hour;
+            let b_cost = (*b_count as f64) * b.price_per_hour;
+            a_cost.partial_cmp(&b_cost).unwrap()
+        })
+}
 
 /// Info to maintain for scaling functions.
 #[derive(Serialize, Deserialize)]
@@ -18,6 +114,116 @@ struct MessagingScalingInfo {
     activity: f64,
     waiting: f64,
     // mem_usage: f64,
+    /// Wall-clock time of the last update, used to compute the decay weight
+    /// for the peak-EWMA estimate on the next rescale.
+    last_update: chrono::DateTime<chrono::Utc>,
+    /// Forward-decaying reservoir of per-interval activity observations,
+    /// used to answer quantile queries (Cormode forward decay).
+    activity_reservoir: Vec<DecayedSample>,
+    /// Landmark time the reservoir's sample weights are relative to.
+    reservoir_landmark: chrono::DateTime<chrono::Utc>,
+    /// Whether we're currently scaled up, per the hysteresis decision below.
+    scaled: bool,
+    /// Wall-clock time of the last transition between scaled and unscaled,
+    /// used to enforce `SCALE_DOWN_COOLDOWN_SECS`.
+    last_scale_change: chrono::DateTime<chrono::Utc>,
+    /// Name of the `InstanceCandidate` shape `cheapest_fit` picked on the
+    /// last rescale, so the chosen heterogeneous shape is observable
+    /// alongside the instance count rather than discarded.
+    selected_instance_type: Option<String>,
+}
+
+/// Peak-biased, time-decayed update of a moving estimate. Spikes (`new` above
+/// `old`) are never smoothed away, only decayed over time; below the current
+/// estimate, `new` is blended in proportionally to how much wall-clock time
+/// `dt_secs` actually elapsed, rather than a fixed blend factor.
+fn peak_ewma_update(old: f64, new: f64, dt_secs: f64) -> f64 {
+    if new > old {
+        new
+    } else {
+        let w = (-dt_secs / EWMA_TAU_SECS).exp();
+        new * (1.0 - w) + old * w
+    }
+}
+
+/// Insert `value` into a forward-decaying reservoir (Cormode forward decay),
+/// rebasing existing weights around a fresh landmark once the current one
+/// gets too old, then evicting the lowest-weight samples once over capacity.
+fn insert_decayed_sample(
+    reservoir: &mut Vec<DecayedSample>,
+    landmark: &mut chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+    value: f64,
+) {
+    let mut dt_landmark = now.signed_duration_since(*landmark).num_seconds() as f64;
+    if dt_landmark > LANDMARK_RESET_SECS {
+        let rebase = (-RESERVOIR_DECAY_LAMBDA * dt_landmark).exp();
+        for sample in reservoir.iter_mut() {
+            sample.weight *= rebase;
+        }
+        *landmark = now;
+        dt_landmark = 0.0;
+    }
+    let weight = (RESERVOIR_DECAY_LAMBDA * dt_landmark).exp();
+    reservoir.push(DecayedSample { value, weight });
+    if reservoir.len() > RESERVOIR_CAPACITY {
+        reservoir.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap());
+        let excess = reservoir.len() - RESERVOIR_CAPACITY;
+        reservoir.drain(0..excess);
+    }
+}
+
+/// Query quantile `q` (in `[0, 1]`) from a forward-decaying reservoir.
+///
+/// Forward decay already does its job on the way in: `insert_decayed_sample`
+/// assigns recent samples the highest weight and uses that to evict stale
+/// ones, so everything still in `reservoir` is "current enough" to count.
+/// Weighting *again* by recency here would do the opposite of what a
+/// robustness quantile is for — it would let the single newest sample (e.g.
+/// one interval of anomalously long requests) dominate the upper tail
+/// precisely when it's freshest. So once a sample has survived eviction, it counts once: this
+/// is a plain nearest-rank quantile over the retained values.
+fn decayed_quantile(reservoir: &[DecayedSample], q: f64) -> f64 {
+    if reservoir.is_empty() {
+        return 0.0;
+    }
+    let mut values: Vec<f64> = reservoir.iter().map(|s| s.value).collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let rank = ((q * values.len() as f64).ceil() as usize).clamp(1, values.len());
+    values[rank - 1]
+}
+
+/// Decide whether the deployment should be scaled, applying hysteresis:
+/// spin up readily once `price_ratio` clears `SCALE_UP_THRESHOLD`, but only
+/// tear down when it is below `SCALE_DOWN_THRESHOLD` and at least
+/// `SCALE_DOWN_COOLDOWN_SECS` have passed since `last_scale_change`. How long
+/// the ratio has been below the threshold is not tracked, only the time since
+/// the last scale change. `force_spin_up` bypasses both bounds and the
+/// cooldown. Returns the new `scaled` state and the `last_scale_change` to
+/// persist alongside it.
+fn apply_hysteresis(
+    force_spin_up: bool,
+    was_scaled: bool,
+    price_ratio: f64,
+    last_scale_change: chrono::DateTime<chrono::Utc>,
+    curr_timestamp: chrono::DateTime<chrono::Utc>,
+) -> (bool, chrono::DateTime<chrono::Utc>) {
+    if force_spin_up {
+        (true, curr_timestamp)
+    } else if !was_scaled && price_ratio >= SCALE_UP_THRESHOLD {
+        (true, curr_timestamp)
+    } else if was_scaled && price_ratio < SCALE_DOWN_THRESHOLD {
+        let since_last_change = curr_timestamp
+            .signed_duration_since(last_scale_change)
+            .num_seconds() as f64;
+        if since_last_change >= SCALE_DOWN_COOLDOWN_SECS {
+            (false, curr_timestamp)
+        } else {
+            (true, last_scale_change)
+        }
+    } else {
+        (was_scaled, last_scale_change)
+    }
 }
 
 /// Rescaler for functions.
@@ -32,7 +238,8 @@ impl MessagingRescaler {
 
 #[async_trait::async_trait]
 impl Rescaler for MessagingRescaler {
-    /// Just compute a moving average.
+    /// Estimate activity and waiting time, then derive a graduated instance
+    /// count from them.
     async fn rescale(
         &self,
         scaling_state: &ServerfulScalingState,
@@ -46,13 +253,43 @@ impl Rescaler for MessagingRescaler {
         let ecs_vcpu = deployed_actor.mem as f64 / 2048.0;
         let lambda_mem = deployed_actor.fn_mem as f64 / 1024.0;
         let caller_mem = deployed_actor.caller_mem as f64 / 1024.0;
+        let scaling_config = serde_json::from_value::<DeploymentScalingConfig>(
+            scaling_state.deployment.clone(),
+        )
+        .unwrap_or_default()
+        .msg_info
+        .unwrap_or_default();
         // Get old activity.
-        let (mut activity, mut waiting) = if let Some(scaling_info) = &scaling_state.scaling_info {
+        let (
+            mut activity,
+            mut waiting,
+            last_update,
+            mut activity_reservoir,
+            mut reservoir_landmark,
+            was_scaled,
+            last_scale_change,
+        ) = if let Some(scaling_info) = &scaling_state.scaling_info {
             let scaling_info: MessagingScalingInfo =
                 serde_json::from_value(scaling_info.clone()).unwrap();
-            (scaling_info.activity, scaling_info.waiting)
+            (
+                scaling_info.activity,
+                scaling_info.waiting,
+                scaling_info.last_update,
+                scaling_info.activity_reservoir,
+                scaling_info.reservoir_landmark,
+                scaling_info.scaled,
+                scaling_info.last_scale_change,
+            )
         } else {
-            (0.0, 0.0)
+            (
+                0.0,
+                0.0,
+                curr_timestamp,
+                Vec::new(),
+                curr_timestamp,
+                false,
+                curr_timestamp,
+            )
         };
         // Compute the total activity of the new metrics.
         let total_interval = curr_timestamp
@@ -78,36 +315,108 @@ impl Rescaler for MessagingRescaler {
             // Average Extra time spent waiting by caller in lambda mode.
             total_waiting_secs += MAX_LAMBDA_OVERHEAD;
         }
-        // Compute moving average.
-        let new_activity = if !force_spin_up {
-            let new_activity = total_active_secs / total_interval;
-            // Limit to 1 (>1 occurs in the presence of parallism).
-            if new_activity > 1.0 {
-                1.0
-            } else {
-                new_activity
-            }
+        // Compute this interval's raw activity, unclamped. Outlier intervals
+        // would badly skew a mean-based estimate; a quantile over the decayed
+        // history is robust to them instead. The force-spin-up sentinel feeds
+        // the peak-EWMA estimate but is kept out of that history below.
+        let raw_new_activity = if !force_spin_up {
+            total_active_secs / total_interval
         } else {
             10.0 // Forcibly spins up a new instance.
         };
+        // No longer clamped to 1: activity above 1 signals genuine
+        // concurrent in-flight work, which now drives a graduated instance
+        // count instead of being discarded.
+        let new_activity = raw_new_activity;
         let new_waiting = total_waiting_secs / total_interval;
-        activity = (1.0 - MOVING_FACTOR) * activity + MOVING_FACTOR * new_activity;
-        waiting = (1.0 - MOVING_FACTOR) * waiting + MOVING_FACTOR * new_waiting;
-        // Compute price ratio.
-        let ecs_cost = ECS_BASE_PRICE * ecs_vcpu;
-        let lambda_cost = 0.0000166667 * 3600.0 * lambda_mem * activity;
+        let dt_secs = curr_timestamp
+            .signed_duration_since(last_update)
+            .num_seconds() as f64;
+        activity = peak_ewma_update(activity, new_activity, dt_secs);
+        waiting = peak_ewma_update(waiting, new_waiting, dt_secs);
+        if !force_spin_up {
+            // Keep the force-spin-up sentinel out of the quantile reservoir
+            // too, not just the graduated-count path below: it's a control
+            // signal, not a real activity sample, and would otherwise sit in
+            // the history for a full decay window.
+            insert_decayed_sample(
+                &mut activity_reservoir,
+                &mut reservoir_landmark,
+                curr_timestamp,
+                raw_new_activity,
+            );
+        }
+        // Use either the mean-based (peak-EWMA) estimate or a quantile over
+        // the decayed activity history, per the deployment's configuration.
+        let scaling_activity = if scaling_config.use_quantile_scaling {
+            let q = scaling_config
+                .activity_quantile
+                .unwrap_or(DEFAULT_ACTIVITY_QUANTILE);
+            decayed_quantile(&activity_reservoir, q)
+        } else {
+            activity
+        };
+        // Candidate ECS shapes to provision, defaulting to the single
+        // baseline shape derived from the deployment's configured memory.
+        let candidates = scaling_config.instance_candidates.clone().unwrap_or_else(|| {
+            vec![InstanceCandidate {
+                name: "default".to_string(),
+                price_per_hour: ECS_BASE_PRICE * ecs_vcpu,
+                weight: 1.0,
+            }]
+        });
+        let cheapest_candidate_cost = candidates
+            .iter()
+            .map(|candidate| candidate.price_per_hour)
+            .fold(f64::INFINITY, f64::min);
+        // Compute price ratio, using the cheapest available shape as the
+        // reference for "is running any ECS capacity worth it at all".
+        let lambda_cost = 0.0000166667 * 3600.0 * lambda_mem * scaling_activity;
         let waiting_cost = 0.0000166667 * 3600.0 * caller_mem * waiting;
-        let price_ratio = (lambda_cost + waiting_cost) / ecs_cost;
-        // Set new scale.
-        let new_scale = u64::from(price_ratio >= 1.0);
-        let new_scaling_info = MessagingScalingInfo { activity, waiting };
+        let price_ratio = (lambda_cost + waiting_cost) / cheapest_candidate_cost;
+        let (scaled, last_scale_change) =
+            apply_hysteresis(force_spin_up, was_scaled, price_ratio, last_scale_change, curr_timestamp);
+        let cheapest_candidate_name = candidates
+            .iter()
+            .min_by(|a, b| a.price_per_hour.partial_cmp(&b.price_per_hour).unwrap())
+            .map(|candidate| candidate.name.clone());
+        // Pick the cheapest instance shape (and count) that covers the
+        // activity at the target utilization, subject to staying within
+        // what the Lambda+waiting cost justifies. `force_spin_up`'s activity
+        // value (10.0) is a control sentinel, not real concurrency, so it
+        // always means exactly one instance of the cheapest shape rather
+        // than feeding through the graduated-count math.
+        let (new_scale, selected_instance_type) = if !scaled {
+            (0, None)
+        } else if force_spin_up {
+            (1, cheapest_candidate_name)
+        } else {
+            match cheapest_fit(&candidates, scaling_activity, lambda_cost + waiting_cost) {
+                Some((candidate, count)) => (count.max(1), Some(candidate.name)),
+                None => (1, cheapest_candidate_name),
+            }
+        };
+        let new_scaling_info = MessagingScalingInfo {
+            activity,
+            waiting,
+            last_update: curr_timestamp,
+            activity_reservoir,
+            reservoir_landmark,
+            selected_instance_type,
+            scaled,
+            last_scale_change,
+        };
         (new_scale, serde_json::to_value(&new_scaling_info).unwrap())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MessagingRescaler, MessagingScalingInfo};
+    use super::{
+        apply_hysteresis, cheapest_fit, decayed_quantile, insert_decayed_sample, InstanceCandidate,
+        MessagingRescaler, MessagingScalingInfo,
+    };
+    use chrono::TimeZone;
     use common::adaptation::frontend::AdapterFrontend;
     use common::adaptation::{AdapterScaling, ScalerReq};
     use common::leasing::Leaser;
@@ -115,6 +424,112 @@ mod tests {
     use serde_json::Value;
     use std::sync::Arc;
 
+    /// A quantile over the decayed reservoir should be robust to a single
+    /// outlier sample (e.g. one interval of anomalously long requests),
+    /// unlike a plain mean.
+    #[test]
+    fn quantile_ignores_single_outlier() {
+        let t0 = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut reservoir = Vec::new();
+        let mut landmark = t0;
+        for i in 0..9 {
+            insert_decayed_sample(
+                &mut reservoir,
+                &mut landmark,
+                t0 + chrono::Duration::seconds(i * 10),
+                0.5,
+            );
+        }
+        // One outlier spike, e.g. from anomalously long request durations.
+        insert_decayed_sample(
+            &mut reservoir,
+            &mut landmark,
+            t0 + chrono::Duration::seconds(100),
+            10.0,
+        );
+        let mean: f64 = reservoir.iter().map(|s| s.value).sum::<f64>() / reservoir.len() as f64;
+        let p90 = decayed_quantile(&reservoir, 0.9);
+        assert!(mean > 1.0, "mean should be dragged up by the outlier: {mean}");
+        assert!(p90 < 1.0, "p90 should be robust to the outlier: {p90}");
+    }
+
+    /// Activity above the target utilization should graduate past a single
+    /// instance rather than being clamped to 0/1.
+    #[test]
+    fn cheapest_fit_graduates_past_one_instance() {
+        let candidates = vec![InstanceCandidate {
+            name: "default".to_string(),
+            price_per_hour: 0.015,
+            weight: 1.0,
+        }];
+        // Activity of 3, well above one instance's target utilization of
+        // 0.75, should require multiple instances, and a big enough budget
+        // should afford them.
+        let (candidate, count) = cheapest_fit(&candidates, 3.0, 1.0).unwrap();
+        assert_eq!(candidate.name, "default");
+        assert!(count > 1, "expected a graduated count, got {count}");
+    }
+
+    /// Among heterogeneous shapes, `cheapest_fit` should pick by total cost
+    /// (count * price), not by per-instance price — a bigger, pricier-per-hour
+    /// shape can still win if fewer of it are needed to cover the activity.
+    #[test]
+    fn cheapest_fit_prefers_lower_total_cost_shape() {
+        let candidates = vec![
+            InstanceCandidate {
+                name: "small".to_string(),
+                price_per_hour: 0.01,
+                weight: 1.0,
+            },
+            InstanceCandidate {
+                name: "large".to_string(),
+                price_per_hour: 0.02,
+                weight: 4.0,
+            },
+        ];
+        // small: ceil(3.0 / (0.75 * 1.0)) = 4 instances, cost 0.04/hr.
+        // large: ceil(3.0 / (0.75 * 4.0)) = 1 instance, cost 0.02/hr.
+        let (candidate, count) = cheapest_fit(&candidates, 3.0, 1.0).unwrap();
+        assert_eq!(candidate.name, "large");
+        assert_eq!(count, 1);
+    }
+
+    /// A high price ratio should scale up immediately, a low ratio should
+    /// not tear down until the cooldown elapses, and it should tear down
+    /// once it has.
+    #[test]
+    fn hysteresis_scale_up_then_cooldown_gated_scale_down() {
+        let t0 = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        // Not yet scaled, ratio clears SCALE_UP_THRESHOLD: scales up immediately.
+        let (scaled, last_scale_change) = apply_hysteresis(false, false, 1.5, t0, t0);
+        assert!(scaled);
+        assert_eq!(last_scale_change, t0);
+
+        // Scaled, ratio drops below SCALE_DOWN_THRESHOLD, but cooldown hasn't
+        // elapsed: stays scaled.
+        let t1 = t0 + chrono::Duration::seconds(60);
+        let (scaled, last_scale_change) = apply_hysteresis(false, true, 0.1, last_scale_change, t1);
+        assert!(scaled, "should stay scaled during the cooldown window");
+        assert_eq!(last_scale_change, t0);
+
+        // Same low ratio, now past the cooldown: tears down.
+        let t2 = t0 + chrono::Duration::seconds(301);
+        let (scaled, last_scale_change) = apply_hysteresis(false, true, 0.1, last_scale_change, t2);
+        assert!(!scaled, "should tear down once the cooldown has elapsed");
+        assert_eq!(last_scale_change, t2);
+    }
+
+    /// `force_spin_up` bypasses both thresholds and the cooldown.
+    #[test]
+    fn hysteresis_force_spin_up_bypasses_cooldown() {
+        let t0 = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let t1 = t0 + chrono::Duration::seconds(1);
+        let (scaled, last_scale_change) = apply_hysteresis(true, false, 0.0, t0, t1);
+        assert!(scaled);
+        assert_eq!(last_scale_change, t1);
+    }
+
     struct TestObject {
         test_start_time: chrono::DateTime<chrono::Utc>,
         scaling: AdapterScaling,
@@ -206,6 +621,12 @@ mod tests {
                 MessagingScalingInfo {
                     activity: 0.0,
                     waiting: 0.0,
+                    last_update: timestamp,
+                    activity_reservoir: Vec::new(),
+                    reservoir_landmark: timestamp,
+                    scaled: false,
+                    last_scale_change: timestamp,
+                    selected_instance_type: None,
                 }
             };
             println!("Checking: lo={activity_lo}; hi={activity_hi};");
@@ -222,21 +643,23 @@ mod tests {
         let mut t = TestObject::new().await;
         // Initial scale should be 0.
         t.check_activity(-0.01, 0.01, Some(false)).await;
-        // Curr=0.5. MA=~0.05
+        // Curr=0.5. Above the old estimate of 0, so peak-EWMA snaps to it.
         t.populate_sleeper_metrics(0.5).await;
-        t.check_activity(0.04, 0.06, Some(false)).await;
-        // Curr=0.5. MA=~0.1
+        t.check_activity(0.45, 0.55, Some(false)).await;
+        // Curr=0.5. Same as the current estimate, no change.
         t.populate_sleeper_metrics(0.5).await;
-        t.check_activity(0.09, 0.11, Some(false)).await;
-        // Curr=10. MA=~1.09.
+        t.check_activity(0.45, 0.55, Some(false)).await;
+        // Curr=10. No longer clamped to 1, so it snaps to the real
+        // concurrent activity (multiple instances worth of work).
         t.populate_sleeper_metrics(10.0).await;
-        t.check_activity(1.05, 1.15, Some(true)).await;
-        // Curr=10. MA=~2.
+        t.check_activity(9.0, 10.0, Some(true)).await;
+        // Curr=10. Same as the current estimate, no change.
         t.populate_sleeper_metrics(10.0).await;
-        t.check_activity(1.95, 2.05, Some(true)).await;
-        // Curr=0.5. MA=~1.85.
+        t.check_activity(9.0, 10.0, Some(true)).await;
+        // Curr=0.5. Below the current estimate, so it only decays towards it
+        // rather than snapping.
         t.populate_sleeper_metrics(0.5).await;
-        t.check_activity(1.8, 1.9, Some(true)).await;
+        t.check_activity(4.0, 9.0, Some(true)).await;
     }
 
     #[tokio::test]